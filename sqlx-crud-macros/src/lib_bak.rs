@@ -6,7 +6,7 @@ use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::{
     parse_macro_input, Attribute, Data, DataStruct, DeriveInput, Field, Fields, FieldsNamed, Ident,
-    Lit, LitStr, Meta, MetaNameValue,
+    Lit, LitStr, Meta, MetaNameValue, NestedMeta,
 };
 
 #[allow(dead_code)] // Usage in quote macros aren't flagged as used
@@ -17,11 +17,52 @@ struct Config<'a> {
     db_ty: DbType,
     model_schema_ident: Ident,
     table_name: String,
-    id_column_ident: Ident,
+    id_column_idents: Vec<Ident>,
     external_id: bool,
+    field_flags: Vec<(Ident, FieldFlags)>,
 }
 
-#[proc_macro_derive(SqlxCrud, attributes(database, id))]
+#[derive(Default, Clone)]
+struct FieldFlags {
+    skip_insert: bool,
+    skip_update: bool,
+    created_at: bool,
+    updated_at: bool,
+    foreign_key: Option<Ident>,
+}
+
+impl FieldFlags {
+    fn from_field(field: &Field) -> Self {
+        let mut flags = Self::default();
+        for attr in field.attrs.iter().filter(|a| a.path.is_ident("sqlx_crud")) {
+            let nested = match attr.parse_meta() {
+                Ok(Meta::List(list)) => list.nested,
+                _ => continue,
+            };
+            for meta in nested {
+                if let NestedMeta::Meta(Meta::Path(path)) = meta {
+                    if path.is_ident("skip_insert") {
+                        flags.skip_insert = true;
+                    } else if path.is_ident("skip_update") {
+                        flags.skip_update = true;
+                    } else if path.is_ident("created_at") {
+                        flags.created_at = true;
+                    } else if path.is_ident("updated_at") {
+                        flags.updated_at = true;
+                    }
+                }
+            }
+        }
+        for attr in field.attrs.iter().filter(|a| a.path.is_ident("foreign_key")) {
+            if let Ok(referenced) = attr.parse_args::<Ident>() {
+                flags.foreign_key = Some(referenced);
+            }
+        }
+        flags
+    }
+}
+
+#[proc_macro_derive(SqlxCrud, attributes(database, id, sqlx_crud, foreign_key))]
 pub fn derive(input: TokenStream) -> TokenStream {
     let DeriveInput {
         ident, data, attrs, ..
@@ -42,7 +83,7 @@ pub fn derive(input: TokenStream) -> TokenStream {
 
                 #static_model_schema
 
-                //#sqlx_crud_impl
+                #sqlx_crud_impl
             }
             .into()
         }
@@ -54,7 +95,6 @@ fn build_static_model_schema(config: &Config) -> TokenStream2 {
     let crate_name = &config.crate_name;
     let model_schema_ident = &config.model_schema_ident;
     let table_name = &config.table_name;
-    let id_column_ident = &config.id_column_ident;
 
     let columns_len = config.named.iter().count();
     let columns = config.named
@@ -62,47 +102,108 @@ fn build_static_model_schema(config: &Config) -> TokenStream2 {
         .flat_map(|f| &f.ident)
         .map(|f| LitStr::new(format!("{}", f).as_str(), f.span()));
 
+    let id_columns_len = config.id_column_idents.len();
+    let id_columns = config.id_column_idents
+        .iter()
+        .map(|f| LitStr::new(format!("{}", f).as_str(), f.span()));
+
     let sql_queries = build_sql_queries(&config);
+    let create_table_sql = build_create_table_sql(&config);
 
     quote! {
         #[automatically_derived]
-        static #model_schema_ident: #crate_name::schema::Metadata<'static, #columns_len> = #crate_name::schema::Metadata {
+        static #model_schema_ident: #crate_name::schema::Metadata<'static, #columns_len, #id_columns_len> = #crate_name::schema::Metadata {
             table_name: #table_name,
-            id_column: #id_column_ident,
+            id_columns: [#(#id_columns),*],
             columns: [#(#columns),*],
+            create_table_sql: #create_table_sql,
             #sql_queries
         };
     }
 }
 
+fn build_create_table_sql(config: &Config) -> String {
+    let single_id = config.id_column_idents.len() == 1;
+
+    let mut column_defs = config.named
+        .iter()
+        .flat_map(|f| f.ident.as_ref().map(|i| (i, &f.ty)))
+        .map(|(ident, ty)| {
+            let is_id = single_id && config.is_id_column(ident);
+            let mut column_def = format!(
+                "{} {}",
+                config.quote_ident(&ident.to_string()),
+                config.db_ty.column_type(ty, is_id)
+            );
+            if let Some(referenced) = config.flags(ident).foreign_key {
+                column_def.push_str(&format!(
+                    " REFERENCES {}({})",
+                    config.quote_ident(&referenced.to_string().to_table_case()),
+                    config.quote_ident("id")
+                ));
+            }
+            column_def
+        })
+        .collect::<Vec<_>>();
+
+    if !single_id {
+        let pk_columns = config.id_column_idents
+            .iter()
+            .map(|id| config.quote_ident(&id.to_string()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        column_defs.push(format!("PRIMARY KEY ({})", pk_columns));
+    }
+
+    format!(
+        "CREATE TABLE IF NOT EXISTS {} ({})",
+        config.quote_ident(&config.table_name),
+        column_defs.join(", ")
+    )
+}
+
 fn build_sql_queries(config: &Config) -> TokenStream2 {
     let table_name = config.quote_ident(&config.table_name);
-    let id_column = format!(
-        "{}.{}",
-        &table_name,
-        config.quote_ident(&config.id_column_ident.to_string())
-    );
-    let insert_bind_cnt = if config.external_id {
-        config.named.iter().count()
-    } else {
-        config.named.iter().count() - 1
-    };
-    let insert_sql_binds = (0..insert_bind_cnt)
-        .map(|_| "?")
+    let id_columns = config.id_column_idents
+        .iter()
+        .map(|id| format!("{}.{}", &table_name, config.quote_ident(&id.to_string())))
+        .collect::<Vec<_>>();
+
+    let mut insert_bind_n = 0;
+    let insert_sql_binds = config.named
+        .iter()
+        .flat_map(|f| &f.ident)
+        .filter(|i| config.insert_participates(i))
+        .map(|i| {
+            if config.insert_is_literal(i) {
+                "CURRENT_TIMESTAMP".to_string()
+            } else {
+                insert_bind_n += 1;
+                config.db_ty.placeholder(insert_bind_n)
+            }
+        })
         .collect::<Vec<_>>()
         .join(", ");
+    let mut update_bind_n = 0;
     let update_sql_binds = config.named
         .iter()
         .flat_map(|f| &f.ident)
-        .filter(|i| *i != &config.id_column_ident)
-        .map(|i| format!("{} = ?", config.quote_ident(&i.to_string())))
+        .filter(|i| config.update_participates(i))
+        .map(|i| {
+            let column = config.quote_ident(&i.to_string());
+            if config.update_is_literal(i) {
+                format!("{} = CURRENT_TIMESTAMP", column)
+            } else {
+                update_bind_n += 1;
+                format!("{} = {}", column, config.db_ty.placeholder(update_bind_n))
+            }
+        })
         .collect::<Vec<_>>()
         .join(", ");
-
     let insert_column_list = config.named
         .iter()
         .flat_map(|f| &f.ident)
-        .filter(|i| !config.external_id && *i != &config.id_column_ident)
+        .filter(|i| config.insert_participates(i))
         .map(|i| config.quote_ident(&i.to_string()))
         .collect::<Vec<_>>()
         .join(", ");
@@ -113,20 +214,35 @@ fn build_sql_queries(config: &Config) -> TokenStream2 {
         .collect::<Vec<_>>()
         .join(", ");
 
+    let by_id_where = |start: usize| {
+        id_columns
+            .iter()
+            .enumerate()
+            .map(|(n, id_column)| {
+                format!("{} = {}", id_column, config.db_ty.placeholder(start + n + 1))
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    };
+
     let select_sql = format!("SELECT {} FROM {}", &column_list, &table_name);
     let select_by_id_sql = format!(
-        "SELECT {} FROM {} WHERE {} = ? LIMIT 1",
-        &column_list, &table_name, &id_column
+        "SELECT {} FROM {} WHERE {} LIMIT 1",
+        &column_list, &table_name, by_id_where(0)
     );
     let insert_sql = format!(
         "INSERT INTO {} ({}) VALUES ({}) RETURNING {}",
         &table_name, &insert_column_list, &insert_sql_binds, &column_list
     );
     let update_by_id_sql = format!(
-        "UPDATE {} SET {} WHERE {} = ? RETURNING {}",
-        &table_name, &update_sql_binds, &id_column, &column_list
+        "UPDATE {} SET {} WHERE {} RETURNING {}",
+        &table_name, &update_sql_binds, &by_id_where(update_bind_n), &column_list
     );
-    let delete_by_id_sql = format!("DELETE FROM {} WHERE {} = ?", &table_name, &id_column);
+    let delete_by_id_sql = format!(
+        "DELETE FROM {} WHERE {}",
+        &table_name, by_id_where(0)
+    );
+    let upsert_sql = build_upsert_sql(config, &table_name, &column_list);
 
     quote! {
         select_sql: #select_sql,
@@ -134,6 +250,99 @@ fn build_sql_queries(config: &Config) -> TokenStream2 {
         insert_sql: #insert_sql,
         update_by_id_sql: #update_by_id_sql,
         delete_by_id_sql: #delete_by_id_sql,
+        upsert_sql: #upsert_sql,
+    }
+}
+
+fn build_upsert_sql(config: &Config, table_name: &str, column_list: &str) -> String {
+    let upsert_columns = config.named
+        .iter()
+        .flat_map(|f| &f.ident)
+        .filter(|i| config.is_id_column(i) || config.insert_participates(i))
+        .collect::<Vec<_>>();
+
+    let mut upsert_bind_n = 0;
+    let upsert_values = upsert_columns
+        .iter()
+        .map(|i| {
+            if config.insert_is_literal(i) {
+                "CURRENT_TIMESTAMP".to_string()
+            } else {
+                upsert_bind_n += 1;
+                config.db_ty.placeholder(upsert_bind_n)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let upsert_column_list = upsert_columns
+        .iter()
+        .map(|i| config.quote_ident(&i.to_string()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let id_column_list = config.id_column_idents
+        .iter()
+        .map(|id| config.quote_ident(&id.to_string()))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let update_set = |excluded: fn(&str) -> String| {
+        config.named
+            .iter()
+            .flat_map(|f| &f.ident)
+            .filter(|i| config.update_participates(i))
+            .map(|i| {
+                let column = config.quote_ident(&i.to_string());
+                if config.update_is_literal(i) {
+                    format!("{} = CURRENT_TIMESTAMP", column)
+                } else {
+                    format!("{} = {}", column, excluded(&column))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match &config.db_ty {
+        DbType::Postgres | DbType::Sqlite | DbType::Any => format!(
+            "INSERT INTO {} ({}) VALUES ({}) ON CONFLICT ({}) DO UPDATE SET {} RETURNING {}",
+            table_name,
+            &upsert_column_list,
+            &upsert_values,
+            &id_column_list,
+            update_set(|column| format!("EXCLUDED.{}", column)),
+            column_list
+        ),
+        DbType::MySql => format!(
+            "INSERT INTO {} ({}) VALUES ({}) ON DUPLICATE KEY UPDATE {}",
+            table_name,
+            &upsert_column_list,
+            &upsert_values,
+            update_set(|column| format!("VALUES({})", column))
+        ),
+        DbType::Mssql => {
+            let on_clause = config.id_column_idents
+                .iter()
+                .map(|id| {
+                    let column = config.quote_ident(&id.to_string());
+                    format!("target.{} = source.{}", column, column)
+                })
+                .collect::<Vec<_>>()
+                .join(" AND ");
+            format!(
+                "MERGE INTO {} AS target USING (VALUES ({})) AS source ({}) ON ({}) \
+                 WHEN MATCHED THEN UPDATE SET {} \
+                 WHEN NOT MATCHED THEN INSERT ({}) VALUES ({}) \
+                 OUTPUT {};",
+                table_name,
+                &upsert_values,
+                &upsert_column_list,
+                &on_clause,
+                update_set(|column| format!("source.{}", column)),
+                &upsert_column_list,
+                &upsert_column_list,
+                column_list
+            )
+        }
     }
 }
 
@@ -141,25 +350,53 @@ fn build_sqlx_crud_impl(config: &Config) -> TokenStream2 {
     let crate_name = &config.crate_name;
     let ident = &config.ident;
     let model_schema_ident = &config.model_schema_ident;
-    let id_column_ident = &config.id_column_ident;
+    let id_column_idents = &config.id_column_idents;
 
-    let id_ty = config.named
-        .iter()
-        .find(|f| f.ident.as_ref() == Some(&config.id_column_ident))
-        .map(|f| &f.ty)
-        .expect("the id type");
+    let id_tys = config.id_column_idents.iter().map(|id| {
+        config.named
+            .iter()
+            .find(|f| f.ident.as_ref() == Some(id))
+            .map(|f| &f.ty)
+            .expect("the id type")
+    });
+    let id_ty = if id_column_idents.len() == 1 {
+        let id_ty = config.named
+            .iter()
+            .find(|f| f.ident.as_ref() == Some(&id_column_idents[0]))
+            .map(|f| &f.ty)
+            .expect("the id type");
+        quote! { #id_ty }
+    } else {
+        quote! { (#(#id_tys),*) }
+    };
+    let id_expr = if id_column_idents.len() == 1 {
+        let id = &id_column_idents[0];
+        quote! { self.#id }
+    } else {
+        quote! { (#(self.#id_column_idents),*) }
+    };
 
     let insert_binds = config.named
         .iter()
         .flat_map(|f| &f.ident)
+        .filter(|i| config.insert_participates(i) && !config.insert_is_literal(i))
         .map(|i| quote! { .bind(&self.#i) });
     let update_binds = config.named
         .iter()
         .flat_map(|f| &f.ident)
-        .filter(|i| *i != &config.id_column_ident)
+        .filter(|i| config.update_participates(i) && !config.update_is_literal(i))
+        .map(|i| quote! { .bind(&self.#i) })
+        .chain(id_column_idents.iter().map(|i| quote! { .bind(&self.#i) }));
+    let upsert_binds = config.named
+        .iter()
+        .flat_map(|f| &f.ident)
+        .filter(|i| (config.is_id_column(i) || config.insert_participates(i)) && !config.insert_is_literal(i))
         .map(|i| quote! { .bind(&self.#i) });
 
     let sqlx_db = config.db_ty.build_sqlx_db();
+    let sqlx_args = config.db_ty.build_sqlx_arguments();
+
+    let fk_helpers = build_foreign_key_helpers(config);
 
     quote! {
         #[automatically_derived]
@@ -171,11 +408,11 @@ fn build_sqlx_crud_impl(config: &Config) -> TokenStream2 {
             }
 
             fn id(&self) -> Self::Id {
-                self.#id_column_ident
+                #id_expr
             }
 
-            fn id_column() -> &'static str {
-                #model_schema_ident.id_column
+            fn id_columns() -> &'static [&'static str] {
+                &#model_schema_ident.id_columns
             }
 
             fn columns() -> &'static [&'static str] {
@@ -201,25 +438,86 @@ fn build_sqlx_crud_impl(config: &Config) -> TokenStream2 {
             fn delete_by_id_sql() -> &'static str {
                 #model_schema_ident.delete_by_id_sql
             }
+
+            fn create_table_sql() -> &'static str {
+                #model_schema_ident.create_table_sql
+            }
+
+            fn upsert_sql() -> &'static str {
+                #model_schema_ident.upsert_sql
+            }
         }
 
         #[automatically_derived]
         impl<'e> #crate_name::traits::Crud<'e, &'e ::sqlx::pool::Pool<#sqlx_db>> for #ident {
             fn insert_binds(
                 &'e self,
-                query: ::sqlx::query::Query<'e, ::sqlx::Sqlite, ::sqlx::sqlite::SqliteArguments<'e>>
-            ) -> ::sqlx::query::Query<'e, ::sqlx::Sqlite, ::sqlx::sqlite::SqliteArguments<'e>> {
+                query: ::sqlx::query::Query<'e, #sqlx_db, #sqlx_args>
+            ) -> ::sqlx::query::Query<'e, #sqlx_db, #sqlx_args> {
                 query
                     #(#insert_binds)*
             }
 
             fn update_binds(
                 &'e self,
-                query: ::sqlx::query::Query<'e, ::sqlx::Sqlite, ::sqlx::sqlite::SqliteArguments<'e>>
-            ) -> ::sqlx::query::Query<'e, ::sqlx::Sqlite, ::sqlx::sqlite::SqliteArguments<'e>> {
+                query: ::sqlx::query::Query<'e, #sqlx_db, #sqlx_args>
+            ) -> ::sqlx::query::Query<'e, #sqlx_db, #sqlx_args> {
                 query
                     #(#update_binds)*
-                    .bind(&self.#id_column_ident)
+            }
+
+            fn upsert_binds(
+                &'e self,
+                query: ::sqlx::query::Query<'e, #sqlx_db, #sqlx_args>
+            ) -> ::sqlx::query::Query<'e, #sqlx_db, #sqlx_args> {
+                query
+                    #(#upsert_binds)*
+            }
+        }
+
+        #fk_helpers
+    }
+}
+
+fn build_foreign_key_helpers(config: &Config) -> TokenStream2 {
+    let ident = &config.ident;
+    let table_name = config.quote_ident(&config.table_name);
+    let column_list = config.named
+        .iter()
+        .flat_map(|f| &f.ident)
+        .map(|i| format!("{}.{}", &table_name, config.quote_ident(&i.to_string())))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let select_sql = format!("SELECT {} FROM {}", &column_list, &table_name);
+
+    let helpers = config.named
+        .iter()
+        .flat_map(|f| f.ident.as_ref())
+        .filter(|i| config.flags(i).foreign_key.is_some())
+        .map(|i| {
+            let fn_name = format_ident!("select_by_{}_sql", i);
+            let sql = format!(
+                "{} WHERE {}.{} = {}",
+                &select_sql,
+                &table_name,
+                config.quote_ident(&i.to_string()),
+                config.db_ty.placeholder(1)
+            );
+            quote! {
+                pub fn #fn_name() -> &'static str {
+                    #sql
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if helpers.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            #[automatically_derived]
+            impl #ident {
+                #(#helpers)*
             }
         }
     }
@@ -246,20 +544,23 @@ impl<'a> Config<'a> {
 
         let table_name = ident.to_string().to_table_case();
 
-        // Search for a field with the #[id] attribute
-        let id_attr = &named
+        // Collect every field carrying the #[id] attribute; a table can have a composite key
+        let id_attrs = named
             .iter()
-            .find(|f| f.attrs.iter().any(|a| a.path.is_ident("id")))
-            .map(|f| f.ident.as_ref())
-            .flatten();
+            .filter(|f| f.attrs.iter().any(|a| a.path.is_ident("id")))
+            .flat_map(|f| f.ident.clone())
+            .collect::<Vec<_>>();
         // Otherwise default to the first field as the "id" column
-        let id_column_ident = id_attr.unwrap_or_else(|| {
-            named
+        let id_column_idents = if id_attrs.is_empty() {
+            vec![named
                 .iter()
                 .flat_map(|f| &f.ident)
                 .next()
                 .expect("the first field")
-        }).clone();
+                .clone()]
+        } else {
+            id_attrs
+        };
 
         let external_id = match attrs
             .iter()
@@ -272,6 +573,11 @@ impl<'a> Config<'a> {
             _ => false,
         };
 
+        let field_flags = named
+            .iter()
+            .flat_map(|f| f.ident.clone().map(|i| (i, FieldFlags::from_field(f))))
+            .collect();
+
         Self {
             ident,
             named,
@@ -279,14 +585,55 @@ impl<'a> Config<'a> {
             db_ty,
             model_schema_ident,
             table_name,
-            id_column_ident,
+            id_column_idents,
             external_id,
+            field_flags,
         }
     }
 
     fn quote_ident(&self, ident: &str) -> String {
         self.db_ty.quote_ident(&ident)
     }
+
+    fn is_id_column(&self, ident: &Ident) -> bool {
+        self.id_column_idents.iter().any(|id| id == ident)
+    }
+
+    fn flags(&self, ident: &Ident) -> FieldFlags {
+        self.field_flags
+            .iter()
+            .find(|(i, _)| i == ident)
+            .map(|(_, flags)| flags.clone())
+            .unwrap_or_default()
+    }
+
+    /// Whether a field is included in the generated INSERT statement at all.
+    fn insert_participates(&self, ident: &Ident) -> bool {
+        if self.is_id_column(ident) && !self.external_id {
+            return false;
+        }
+        !self.flags(ident).skip_insert
+    }
+
+    /// Whether a participating INSERT field is a `CURRENT_TIMESTAMP` literal rather than a bind.
+    fn insert_is_literal(&self, ident: &Ident) -> bool {
+        let flags = self.flags(ident);
+        flags.created_at || flags.updated_at
+    }
+
+    /// Whether a field is included in the generated UPDATE statement's SET list.
+    fn update_participates(&self, ident: &Ident) -> bool {
+        if self.is_id_column(ident) {
+            return false;
+        }
+        let flags = self.flags(ident);
+        !(flags.skip_update || flags.created_at)
+    }
+
+    /// Whether a participating UPDATE field is a `CURRENT_TIMESTAMP` literal rather than a bind.
+    fn update_is_literal(&self, ident: &Ident) -> bool {
+        self.flags(ident).updated_at
+    }
 }
 
 enum DbType {
@@ -334,13 +681,109 @@ impl DbType {
         }
     }
 
+    fn build_sqlx_arguments(&self) -> TokenStream2 {
+        match self {
+            Self::Any => quote! { ::sqlx::any::AnyArguments<'e> },
+            Self::Mssql => quote! { ::sqlx::mssql::MssqlArguments },
+            Self::MySql => quote! { ::sqlx::mysql::MySqlArguments },
+            Self::Postgres => quote! { ::sqlx::postgres::PgArguments },
+            Self::Sqlite => quote! { ::sqlx::sqlite::SqliteArguments<'e> },
+        }
+    }
+
     fn quote_ident(&self, ident: &str) -> String {
         match self {
-            Self::Any => format!(r#""{}""#, &ident),
-            Self::Mssql => format!(r#""{}""#, &ident),
-            Self::MySql => format!("`{}`", &ident),
-            Self::Postgres => format!(r#""{}""#, &ident),
-            Self::Sqlite => format!(r#""{}""#, &ident),
+            Self::MySql => format!("`{}`", ident.replace('`', "``")),
+            Self::Any | Self::Mssql | Self::Postgres | Self::Sqlite => {
+                format!(r#""{}""#, ident.replace('"', "\"\""))
+            }
         }
     }
+
+    fn placeholder(&self, n: usize) -> String {
+        match self {
+            Self::Any => "?".to_string(),
+            Self::Mssql => format!("@p{}", n),
+            Self::MySql => "?".to_string(),
+            Self::Postgres => format!("${}", n),
+            Self::Sqlite => "?".to_string(),
+        }
+    }
+
+    /// Maps a Rust field type to a backend column type, peeling off `Option<T>` to decide
+    /// nullability and marking the `#[id]` column(s) as the primary key (for single-column keys).
+    fn column_type(&self, ty: &syn::Type, is_id: bool) -> String {
+        let (inner, nullable) = unwrap_option(ty);
+        let base = self.base_column_type(&rust_type_ident(inner), is_id);
+        if is_id {
+            format!("{} PRIMARY KEY", base)
+        } else if nullable {
+            base
+        } else {
+            format!("{} NOT NULL", base)
+        }
+    }
+
+    fn base_column_type(&self, rust_ty: &str, is_id: bool) -> String {
+        match rust_ty {
+            "i8" | "i16" | "i32" | "u8" | "u16" | "u32" => match self {
+                Self::Postgres if is_id => "SERIAL".to_string(),
+                Self::MySql | Self::Mssql => "INT".to_string(),
+                _ => "INTEGER".to_string(),
+            },
+            "i64" | "u64" | "isize" | "usize" => match self {
+                Self::Postgres if is_id => "BIGSERIAL".to_string(),
+                Self::Sqlite => "INTEGER".to_string(),
+                _ => "BIGINT".to_string(),
+            },
+            "f32" => "REAL".to_string(),
+            "f64" => match self {
+                Self::Postgres => "DOUBLE PRECISION".to_string(),
+                Self::MySql | Self::Mssql => "DOUBLE".to_string(),
+                _ => "REAL".to_string(),
+            },
+            "bool" => match self {
+                Self::Mssql => "BIT".to_string(),
+                _ => "BOOLEAN".to_string(),
+            },
+            "Vec" => match self {
+                Self::Postgres => "BYTEA".to_string(),
+                Self::Mssql => "VARBINARY(MAX)".to_string(),
+                _ => "BLOB".to_string(),
+            },
+            _ => match self {
+                Self::MySql => "VARCHAR(255)".to_string(),
+                Self::Mssql => "NVARCHAR(MAX)".to_string(),
+                _ => "TEXT".to_string(),
+            },
+        }
+    }
+}
+
+/// Returns `T` if `ty` is `Option<T>` (only one `Option` layer is removed), otherwise `ty` itself,
+/// together with whether `ty` was `Option<T>`, so callers can decide nullability.
+fn unwrap_option(ty: &syn::Type) -> (&syn::Type, bool) {
+    if let syn::Type::Path(syn::TypePath { path, .. }) = ty {
+        if let Some(segment) = path.segments.last() {
+            if segment.ident == "Option" {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                        return (inner, true);
+                    }
+                }
+            }
+        }
+    }
+    (ty, false)
+}
+
+fn rust_type_ident(ty: &syn::Type) -> String {
+    match ty {
+        syn::Type::Path(syn::TypePath { path, .. }) => path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
 }
\ No newline at end of file